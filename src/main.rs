@@ -4,9 +4,10 @@
 use clap::{Parser, Subcommand};
 use gstreamer::prelude::*;
 use std::{
+    collections::VecDeque,
     process::ExitStatus,
     sync::{
-        Arc,
+        Arc, Mutex,
         atomic::{AtomicBool, Ordering},
         mpsc,
     },
@@ -22,7 +23,7 @@ use wry::WebViewExtMacOS;
 use wry::dpi::Size;
 use wry::{WebView, WebViewBuilder};
 
-fn process_png_data(png_data: Vec<u8>) {
+fn process_png_data(png_data: Vec<u8>, preview: Option<PreviewMode>, cell_ratio: f64) {
     if png_data.is_empty() {
         println!("No PNG data received");
     } else {
@@ -31,11 +32,123 @@ fn process_png_data(png_data: Vec<u8>) {
             .unwrap();
 
         rgb.save("output.png").unwrap();
+
+        if let Some(mode) = preview {
+            let mode = match mode {
+                PreviewMode::Auto => {
+                    let is_kitty = std::env::var("TERM")
+                        .map(|term| term.contains("kitty"))
+                        .unwrap_or(false);
+                    if is_kitty {
+                        PreviewMode::Kitty
+                    } else {
+                        PreviewMode::Sixel
+                    }
+                }
+                other => other,
+            };
+
+            match mode {
+                PreviewMode::Kitty => preview_kitty(&png_data),
+                PreviewMode::Sixel => preview_sixel(&rgb, cell_ratio),
+                PreviewMode::Auto => unreachable!(),
+            }
+        }
+
         std::process::exit(1);
         println!("Screenshot saved as output.png");
     }
 }
 
+/// Paint raw PNG bytes into a kitty-graphics-protocol-capable terminal, letting the
+/// terminal itself decode the PNG rather than shipping decoded pixels over the escape
+/// sequence.
+fn preview_kitty(png_data: &[u8]) {
+    use base64::Engine;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(png_data);
+    let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(4096).collect();
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = if i + 1 == chunks.len() { 0 } else { 1 };
+        if i == 0 {
+            print!(
+                "\x1b_Gf=100,a=T,m={};{}\x1b\\",
+                more,
+                std::str::from_utf8(chunk).unwrap()
+            );
+        } else {
+            print!("\x1b_Gm={};{}\x1b\\", more, std::str::from_utf8(chunk).unwrap());
+        }
+    }
+    println!();
+}
+
+/// Downscale the decoded image to the terminal's cell grid and emit it as sixel bands.
+/// `cell_ratio` (terminal cell width/height, typically ~0.46) corrects for cells being
+/// taller than they are wide so the image isn't vertically stretched.
+fn preview_sixel(image: &image::DynamicImage, cell_ratio: f64) {
+    let (target_width, term_height_px) = terminal_pixel_size();
+    // Correct for cells being taller than they are wide so the image isn't stretched.
+    let target_height = (term_height_px as f64 * cell_ratio) as u32;
+
+    let scaled = image.resize(
+        target_width.max(1),
+        target_height.max(1),
+        image::imageops::FilterType::Triangle,
+    );
+
+    if let Err(e) = render_sixel(&scaled) {
+        eprintln!("Failed to render sixel preview: {}", e);
+    }
+}
+
+/// Approximate pixel dimensions of the terminal. `TIOCGWINSZ` reports the real pixel
+/// size (`ws_xpixel`/`ws_ypixel`) on terminals that populate it; most don't, so fall
+/// back to estimating from the character grid with a representative monospace cell.
+fn terminal_pixel_size() -> (u32, u32) {
+    unsafe {
+        let mut ws: libc::winsize = std::mem::zeroed();
+        if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut ws) == 0 {
+            if ws.ws_xpixel > 0 && ws.ws_ypixel > 0 {
+                return (ws.ws_xpixel as u32, ws.ws_ypixel as u32);
+            }
+            if ws.ws_col > 0 && ws.ws_row > 0 {
+                const CELL_PIXEL_WIDTH: u32 = 10;
+                const CELL_PIXEL_HEIGHT: u32 = 20;
+                return (
+                    ws.ws_col as u32 * CELL_PIXEL_WIDTH,
+                    ws.ws_row as u32 * CELL_PIXEL_HEIGHT,
+                );
+            }
+        }
+    }
+    // Fall back to a conservative default when we can't query the terminal.
+    (800, 480)
+}
+
+/// Encode the image as sixel bands and write them directly to stdout; libsixel's
+/// `Encoder` writes to its output sink itself rather than handing back a string.
+fn render_sixel(image: &image::DynamicImage) -> Result<(), Box<dyn std::error::Error>> {
+    use sixel_rs::encoder::{Encoder, QuickFrameBuilder};
+    use sixel_rs::optflags::EncodePolicy;
+
+    let rgba = image.to_rgba8();
+    let (width, height) = rgba.dimensions();
+
+    let encoder = Encoder::new()?;
+    encoder.set_encode_policy(EncodePolicy::Auto)?;
+
+    let frame = QuickFrameBuilder::new()
+        .width(width as usize)
+        .height(height as usize)
+        .format(sixel_rs::sys::PixelFormat::RGBA8888)
+        .pixels(rgba.into_raw());
+
+    encoder.encode_bytes(frame)?;
+    Ok(())
+}
+
 #[derive(Parser)]
 #[command(name = "webview-recorder")]
 #[command(about = "A webview recording and capturing tool")]
@@ -49,6 +162,42 @@ struct Cli {
     verbosity: u8,
 }
 
+#[derive(Clone, Copy, clap::ValueEnum, PartialEq)]
+enum TimestampMode {
+    /// Derive PTS from the requested fps regardless of how long capture actually took
+    Fps,
+    /// Stamp each frame with the real elapsed time since start, duplicating/dropping
+    /// frames as needed to stay honest about observed capture latency
+    CaptureTime,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Codec {
+    H264,
+    H265,
+    Av1,
+}
+
+/// Quality/speed knobs threaded from the `Record` CLI args into the encoder element.
+#[derive(Clone)]
+pub struct CodecOptions {
+    pub codec: Codec,
+    pub crf: u32,
+    /// 0 means "one thread per CPU", resolved via `num_cpus`.
+    pub threads: u32,
+    pub max_frame_delay: Option<u32>,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum PreviewMode {
+    /// Kitty graphics protocol (chunked base64 escape sequences)
+    Kitty,
+    /// Sixel bands, downscaled to the target cell grid
+    Sixel,
+    /// Kitty if `$TERM` mentions kitty, sixel otherwise
+    Auto,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Capture a single screenshot of the webview
@@ -57,9 +206,18 @@ enum Commands {
         #[arg(short, long, default_value = "1920")]
         width: u32,
 
-        /// Height of the webview window  
+        /// Height of the webview window
         #[arg(short, long, default_value = "1080")]
         height: u32,
+
+        /// Also render the screenshot directly into the terminal; output.png is still
+        /// written either way
+        #[arg(long, value_enum)]
+        preview: Option<PreviewMode>,
+
+        /// Terminal cell width/height ratio, used to keep sixel previews from stretching
+        #[arg(long, default_value = "0.46")]
+        cell_ratio: f64,
     },
     /// Record a video of the webview
     Record {
@@ -74,6 +232,67 @@ enum Commands {
         /// Frames per second for recording
         #[arg(short, long, default_value = "30")]
         fps: u16,
+
+        /// Capture audio from the default input device (macOS only) and mux it alongside
+        /// the video track; this is not the webview's own page audio, and on other
+        /// platforms no audio is captured
+        #[arg(long, default_value_t = false)]
+        audio: bool,
+
+        /// Video codec to encode with
+        #[arg(long, value_enum, default_value = "h265")]
+        codec: Codec,
+
+        /// Constant rate factor (quality/size tradeoff; lower is higher quality)
+        #[arg(long, default_value = "18")]
+        crf: u32,
+
+        /// Encoder thread count; 0 means one thread per CPU
+        #[arg(long, default_value = "0")]
+        threads: u32,
+
+        /// Bound the encoder's look-ahead buffer (in frames) so low-latency recordings
+        /// don't buffer hundreds of frames, when the codec supports it
+        #[arg(long)]
+        max_frame_delay: Option<u32>,
+
+        /// How to derive each frame's PTS
+        #[arg(long, value_enum, default_value = "fps")]
+        timestamp: TimestampMode,
+
+        /// Publish each captured frame over a TCP socket (host:port) as a
+        /// length-prefixed buffer, in addition to writing the recorded file, so other
+        /// processes can consume the webview's frames in real time
+        #[arg(long)]
+        fanout_addr: Option<String>,
+
+        /// JPEG-encode frames before publishing them over --fanout-addr. Encoding runs
+        /// inline on the fan-out thread, so it adds latency to publishing but never
+        /// blocks capture itself
+        #[arg(long, default_value_t = false)]
+        fanout_jpeg: bool,
+    },
+    /// Stream the webview out over NDI instead of recording to a file
+    Stream {
+        /// Width of the webview window
+        #[arg(short, long, default_value = "1920")]
+        width: u32,
+
+        /// Height of the webview window
+        #[arg(short, long, default_value = "1080")]
+        height: u32,
+
+        /// Frames per second for streaming
+        #[arg(short, long, default_value = "30")]
+        fps: u16,
+
+        /// Name the stream is advertised under on the NDI network
+        #[arg(long, default_value = "wringer")]
+        ndi_name: String,
+
+        /// Honor appsrc timestamps against the NDI clock instead of the system clock
+        #[arg(long, default_value_t = false)]
+        clock_sync: bool,
     },
 }
 
@@ -81,23 +300,71 @@ fn main() -> wry::Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Capture { width, height } => {
+        Commands::Capture {
+            width,
+            height,
+            preview,
+            cell_ratio,
+        } => {
             if cli.verbosity > 0 {
                 println!(
                     "Starting capture mode with dimensions: {}x{}",
                     width, height
                 );
             }
-            run_capture(width, height, cli.verbosity)
+            run_capture(width, height, preview, cell_ratio, cli.verbosity)
         }
-        Commands::Record { width, height, fps } => {
+        Commands::Record {
+            width,
+            height,
+            fps,
+            audio,
+            codec,
+            crf,
+            threads,
+            max_frame_delay,
+            timestamp,
+            fanout_addr,
+            fanout_jpeg,
+        } => {
             if cli.verbosity > 0 {
                 println!(
                     "Starting record mode with dimensions: {}x{} at {} FPS",
                     width, height, fps
                 );
             }
-            run_record(width, height, fps, cli.verbosity)
+            let codec_options = CodecOptions {
+                codec,
+                crf,
+                threads,
+                max_frame_delay,
+            };
+            run_record(
+                width,
+                height,
+                fps,
+                audio,
+                codec_options,
+                timestamp,
+                fanout_addr,
+                fanout_jpeg,
+                cli.verbosity,
+            )
+        }
+        Commands::Stream {
+            width,
+            height,
+            fps,
+            ndi_name,
+            clock_sync,
+        } => {
+            if cli.verbosity > 0 {
+                println!(
+                    "Starting NDI stream \"{}\" with dimensions: {}x{} at {} FPS",
+                    ndi_name, width, height, fps
+                );
+            }
+            run_stream(width, height, fps, ndi_name, clock_sync, cli.verbosity)
         }
     }
 }
@@ -151,7 +418,13 @@ fn build_webview(width: u32, height: u32) -> wry::Result<(WebView, EventLoop<()>
     Ok((webview, event_loop))
 }
 
-fn run_capture(width: u32, height: u32, verbosity: u8) -> wry::Result<()> {
+fn run_capture(
+    width: u32,
+    height: u32,
+    preview: Option<PreviewMode>,
+    cell_ratio: f64,
+    verbosity: u8,
+) -> wry::Result<()> {
     let (webview, event_loop) = build_webview(width, height)?;
     let mut active_webview = false;
 
@@ -183,7 +456,7 @@ fn run_capture(width: u32, height: u32, verbosity: u8) -> wry::Result<()> {
 
                     exit_flag.store(true, Ordering::SeqCst);
 
-                    process_png_data(png_data);
+                    process_png_data(png_data, preview, cell_ratio);
                 })
                 .unwrap();
         }
@@ -200,7 +473,139 @@ fn run_capture(width: u32, height: u32, verbosity: u8) -> wry::Result<()> {
     });
 }
 
-fn run_record(width: u32, height: u32, fps: u16, verbosity: u8) -> wry::Result<()> {
+/// Header prefixed to each frame published over the fan-out socket, immediately
+/// followed by a little-endian `u32` payload length and then the payload itself.
+struct FrameHeader {
+    index: u32,
+    timestamp_ns: u64,
+    width: u32,
+    height: u32,
+    format: u8, // 0 = png, 1 = jpeg
+}
+
+impl FrameHeader {
+    fn to_bytes(&self) -> [u8; 21] {
+        let mut buf = [0u8; 21];
+        buf[0..4].copy_from_slice(&self.index.to_le_bytes());
+        buf[4..12].copy_from_slice(&self.timestamp_ns.to_le_bytes());
+        buf[12..16].copy_from_slice(&self.width.to_le_bytes());
+        buf[16..20].copy_from_slice(&self.height.to_le_bytes());
+        buf[20] = self.format;
+        buf
+    }
+}
+
+/// Publish captured frames on a TCP socket for other processes (monitors, ML
+/// pipelines) to subscribe to in real time, independent of the recorded file. Accepts
+/// any number of clients and broadcasts every frame to each of them; a client that
+/// disconnects is just dropped from the broadcast list.
+fn spawn_frame_fanout(
+    addr: String,
+    jpeg: bool,
+    width: u32,
+    height: u32,
+    rx: mpsc::Receiver<(Vec<u8>, u64)>,
+) {
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    thread::spawn(move || {
+        let listener = match TcpListener::bind(&addr) {
+            Ok(listener) => listener,
+            Err(e) => {
+                eprintln!("Failed to bind fan-out socket on {}: {}", addr, e);
+                return;
+            }
+        };
+        listener.set_nonblocking(true).ok();
+        println!("Publishing frames on {}", addr);
+
+        let (new_clients_tx, new_clients_rx) = mpsc::channel::<std::net::TcpStream>();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        stream.set_nodelay(true).ok();
+                        if new_clients_tx.send(stream).is_err() {
+                            break;
+                        }
+                    }
+                    Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        thread::sleep(Duration::from_millis(50));
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+
+        // A single dedicated writer thread owns the client list and serializes every
+        // frame write to every client, so frames can never be written out of order and
+        // a slow client never stalls the encode loop; writes go to one client after
+        // another, so a slow client does delay delivery to the clients after it.
+        let (frames_tx, frames_rx) = mpsc::channel::<(FrameHeader, Vec<u8>)>();
+        thread::spawn(move || {
+            let mut clients: Vec<std::net::TcpStream> = Vec::new();
+
+            while let Ok((header, payload)) = frames_rx.recv() {
+                while let Ok(stream) = new_clients_rx.try_recv() {
+                    clients.push(stream);
+                }
+
+                let len = (payload.len() as u32).to_le_bytes();
+                clients.retain_mut(|stream| {
+                    stream.write_all(&header.to_bytes()).is_ok()
+                        && stream.write_all(&len).is_ok()
+                        && stream.write_all(&payload).is_ok()
+                });
+            }
+        });
+
+        let mut index: u32 = 0;
+
+        while let Ok((png_data, timestamp_ns)) = rx.recv() {
+            let (payload, format) = if jpeg {
+                match image::load_from_memory(&png_data) {
+                    Ok(img) => {
+                        let mut jpeg_bytes = Vec::new();
+                        let mut cursor = std::io::Cursor::new(&mut jpeg_bytes);
+                        match img.write_to(&mut cursor, image::ImageFormat::Jpeg) {
+                            Ok(()) => (jpeg_bytes, 1u8),
+                            Err(_) => (png_data, 0u8),
+                        }
+                    }
+                    Err(_) => (png_data, 0u8),
+                }
+            } else {
+                (png_data, 0u8)
+            };
+
+            let header = FrameHeader {
+                index,
+                timestamp_ns,
+                width,
+                height,
+                format,
+            };
+            index += 1;
+
+            if frames_tx.send((header, payload)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn run_record(
+    width: u32,
+    height: u32,
+    fps: u16,
+    audio: bool,
+    codec_options: CodecOptions,
+    timestamp_mode: TimestampMode,
+    fanout_addr: Option<String>,
+    fanout_jpeg: bool,
+    verbosity: u8,
+) -> wry::Result<()> {
     let (webview, event_loop) = build_webview(width, height)?;
     // Track when we started and whether we've taken the screenshot
     let start_time = Instant::now();
@@ -212,37 +617,67 @@ fn run_record(width: u32, height: u32, fps: u16, verbosity: u8) -> wry::Result<(
 
     let (tx, rx) = mpsc::channel::<(Vec<u8>, u64)>();
 
-    let encoder = PngVideoEncoder::new(
-        "output.mkv",
+    let fanout_tx = fanout_addr.map(|addr| {
+        let (fanout_tx, fanout_rx) = mpsc::channel::<(Vec<u8>, u64)>();
+        spawn_frame_fanout(addr, fanout_jpeg, width, height, fanout_rx);
+        fanout_tx
+    });
+
+    let encoder = VideoEncoder::new(
+        VideoSink::File {
+            path: "output.mkv".to_string(),
+            codec_options,
+        },
         width,
         height,
         gst::Fraction::new(fps as i32, 1),
+        audio,
     )
     .unwrap();
+    let encoder = Arc::new(encoder);
 
     let frame_duration: Duration = Duration::from_millis(1000 / fps as u64);
 
     // Start encoder in a separate thread
+    let video_encoder = encoder.clone();
     let encoder_handle = thread::spawn(move || {
-        encoder.start().unwrap();
+        video_encoder.start().unwrap();
         while let Ok((png_data, timestamp)) = rx.recv() {
-            println!("{}", timestamp);
             if png_data.is_empty() || timestamp == 0 {
-                println!("stopping");
-                encoder.finish().unwrap();
+                video_encoder.finish().unwrap();
 
                 break; // Signal to stop
             }
             let static_data: &'static [u8] = Box::leak(png_data.into_boxed_slice());
-            println!("whattt");
-            encoder
+            video_encoder
                 .push_png_buffer_with_timestamp(static_data, timestamp)
                 .unwrap();
         }
     });
 
+    // The Instant video PTS=0 is measured from, so audio can be stamped against the
+    // same base: `capture-time` mode already zeroes against `start_time`, but `fps`
+    // mode's PTS is a synthetic per-frame counter that only starts once the first
+    // frame is actually captured (well after the webview warmup `start_time` predates).
+    let pts_base: Arc<Mutex<Option<Instant>>> = Arc::new(Mutex::new(match timestamp_mode {
+        TimestampMode::CaptureTime => Some(start_time),
+        TimestampMode::Fps => None,
+    }));
+
+    if audio {
+        spawn_audio_capture(encoder.clone(), pts_base.clone());
+    }
+
     let mut active_webview = false;
 
+    let frame_duration_ns = 1_000_000_000u64 / fps as u64;
+    // Observed capture-time PTS of the last frame we actually pushed, and a short
+    // history of recent frames so a missed capture slot can be filled by re-pushing
+    // the most recent one rather than letting the video silently speed up.
+    let last_pts = Arc::new(Mutex::new(0u64));
+    let pending_frames: Arc<Mutex<VecDeque<(Vec<u8>, u64)>>> =
+        Arc::new(Mutex::new(VecDeque::with_capacity(4)));
+
     // Run the event loop
     event_loop.run(move |event, _, control_flow| {
         *control_flow = ControlFlow::Poll; // Use Poll to keep checking time
@@ -251,7 +686,20 @@ fn run_record(width: u32, height: u32, fps: u16, verbosity: u8) -> wry::Result<(
 
         // Check if 5 seconds have passed and we haven't taken the screenshot yet
         if (now.duration_since(last_frame_time) >= frame_duration) && active_webview {
+            {
+                let mut pts_base = pts_base.lock().unwrap();
+                if pts_base.is_none() {
+                    // `fps` mode stamps the first frame with PTS = 1 * frame_duration_ns,
+                    // so PTS=0 lies one frame before this capture.
+                    let frame = Duration::from_nanos(frame_duration_ns);
+                    *pts_base = Some(now.checked_sub(frame).unwrap_or(now));
+                }
+            }
+
             let tx_clone = tx.clone();
+            let last_pts = last_pts.clone();
+            let pending_frames = pending_frames.clone();
+            let fanout_tx_clone = fanout_tx.clone();
             webview
                 .take_snapshot(None, move |result| {
                     let png_data = match result {
@@ -262,27 +710,228 @@ fn run_record(width: u32, height: u32, fps: u16, verbosity: u8) -> wry::Result<(
                         }
                     };
 
-                    // let static_data: &'static [u8] = Box::leak(png_data.into_boxed_slice());
-                    // encoder.push_png_buffer(static_data);
-                    // process_png_data(png_data);
-                    let timestamp_ns = count as u64 * (1_000_000_000 / fps as u64);
+                    let timestamp_ns = match timestamp_mode {
+                        TimestampMode::Fps => count as u64 * frame_duration_ns,
+                        TimestampMode::CaptureTime => {
+                            let elapsed_ns = start_time.elapsed().as_nanos() as u64;
+                            let mut last_pts = last_pts.lock().unwrap();
+
+                            // GStreamer requires PTS never go backward; a frame that
+                            // arrives "before" the last one we emitted is dropped.
+                            if elapsed_ns <= *last_pts {
+                                return;
+                            }
+
+                            let mut pending = pending_frames.lock().unwrap();
+                            if let Some((last_data, _)) = pending.back() {
+                                let mut fill_ts = *last_pts + frame_duration_ns;
+                                while fill_ts + frame_duration_ns <= elapsed_ns {
+                                    let _ = tx_clone.send((last_data.clone(), fill_ts));
+                                    fill_ts += frame_duration_ns;
+                                }
+                            }
+
+                            pending.push_back((png_data.clone(), elapsed_ns));
+                            if pending.len() > 4 {
+                                pending.pop_front();
+                            }
+
+                            *last_pts = elapsed_ns;
+                            elapsed_ns
+                        }
+                    };
+
+                    if let Some(fanout_tx) = &fanout_tx_clone {
+                        let _ = fanout_tx.send((png_data.clone(), timestamp_ns));
+                    }
+
                     let _ = tx_clone.send((png_data, timestamp_ns));
                 })
                 .unwrap();
 
             last_frame_time = now;
+            count += 1;
+        }
 
-            if count == 300 {
-                println!("{}", start_time.elapsed().as_millis())
+        match event {
+            Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                ..
+            } => {
+                let _ = tx.send((Vec::new(), 0u64));
+                *control_flow = ControlFlow::Exit;
+            }
+            Event::RedrawRequested(_) => {
+                active_webview = true;
             }
+            _ => (),
+        }
+    });
+}
 
-            if count == 400 {
-                let _ = tx.send((Vec::new(), 0u64));
-                println!("end reached");
+/// Capture audio and forward it into `encoder`'s audio appsrc, stamping each buffer
+/// against `pts_base` so it lines up with whatever instant the active timestamp mode
+/// treats as video PTS=0 (samples captured before `pts_base` is set are dropped).
+///
+/// NOTE: `osxaudiosrc` opens the default *input* device (microphone), not the system's
+/// audio *output*. There is no portable loopback/output-capture element in stock
+/// GStreamer, so this records whatever the mic picks up, not the webview's page audio.
+/// A real fix needs a loopback driver (e.g. BlackHole/Soundflower) selected by name via
+/// `osxaudiosrc`'s `device` property, or platform-specific output-capture APIs.
+#[cfg(target_os = "macos")]
+fn spawn_audio_capture(encoder: Arc<VideoEncoder>, pts_base: Arc<Mutex<Option<Instant>>>) {
+    thread::spawn(move || {
+        let audiosrc = match gst::ElementFactory::make("osxaudiosrc").build() {
+            Ok(e) => e,
+            Err(e) => {
+                eprintln!("Audio capture unavailable: {}", e);
+                return;
             }
+        };
+        let audioconvert = gst::ElementFactory::make("audioconvert").build().unwrap();
+        let audioresample = gst::ElementFactory::make("audioresample").build().unwrap();
+        let capsfilter = gst::ElementFactory::make("capsfilter").build().unwrap();
+        capsfilter.set_property(
+            "caps",
+            &gst::Caps::builder("audio/x-raw")
+                .field("format", &"S16LE")
+                .field("rate", &48000i32)
+                .field("channels", &2i32)
+                .build(),
+        );
+
+        let appsink = gst::ElementFactory::make("appsink")
+            .build()
+            .unwrap()
+            .downcast::<gst_app::AppSink>()
+            .unwrap();
+        appsink.set_property("emit-signals", &true);
+        appsink.set_property("sync", &false);
+
+        let pipeline = gst::Pipeline::new();
+        pipeline
+            .add_many(&[
+                &audiosrc,
+                &audioconvert,
+                &audioresample,
+                &capsfilter,
+                &appsink.clone().upcast::<gst::Element>(),
+            ])
+            .unwrap();
+        gst::Element::link_many(&[
+            &audiosrc,
+            &audioconvert,
+            &audioresample,
+            &capsfilter,
+            &appsink.clone().upcast::<gst::Element>(),
+        ])
+        .unwrap();
+
+        appsink.set_callbacks(
+            gst_app::AppSinkCallbacks::builder()
+                .new_sample(move |sink| {
+                    let sample = sink.pull_sample().map_err(|_| gst::FlowError::Eos)?;
+                    let buffer = sample.buffer().ok_or(gst::FlowError::Error)?;
+                    let map = buffer.map_readable().map_err(|_| gst::FlowError::Error)?;
+
+                    let timestamp_ns = {
+                        let base = pts_base.lock().unwrap();
+                        match *base {
+                            Some(base) => base.elapsed().as_nanos() as u64,
+                            // No video frame has been captured yet; this sample predates
+                            // the recording's PTS=0 and has nothing to sync against.
+                            None => return Ok(gst::FlowSuccess::Ok),
+                        }
+                    };
+                    let static_data: &'static [u8] = Box::leak(map.as_slice().to_vec().into_boxed_slice());
+                    let _ = encoder.push_audio_buffer_with_timestamp(static_data, timestamp_ns);
+
+                    Ok(gst::FlowSuccess::Ok)
+                })
+                .build(),
+        );
+
+        pipeline.set_state(gst::State::Playing).unwrap();
+    });
+}
+
+#[cfg(not(target_os = "macos"))]
+fn spawn_audio_capture(_encoder: Arc<VideoEncoder>, _pts_base: Arc<Mutex<Option<Instant>>>) {
+    eprintln!("Audio capture is only wired up for macOS right now; recording video only");
+}
+
+fn run_stream(
+    width: u32,
+    height: u32,
+    fps: u16,
+    ndi_name: String,
+    clock_sync: bool,
+    _verbosity: u8,
+) -> wry::Result<()> {
+    let (webview, event_loop) = build_webview(width, height)?;
+    let start_time = Instant::now();
+    let mut last_frame_time = Instant::now();
+
+    println!("Starting webview, streaming to NDI as \"{}\"...", ndi_name);
+
+    let mut count = 1;
 
+    let (tx, rx) = mpsc::channel::<(Vec<u8>, u64)>();
+
+    let encoder = VideoEncoder::new(
+        VideoSink::Ndi {
+            name: ndi_name,
+            clock_sync,
+        },
+        width,
+        height,
+        gst::Fraction::new(fps as i32, 1),
+        false,
+    )
+    .unwrap();
+
+    let frame_duration: Duration = Duration::from_millis(1000 / fps as u64);
+
+    let encoder_handle = thread::spawn(move || {
+        encoder.start().unwrap();
+        while let Ok((png_data, timestamp)) = rx.recv() {
+            if png_data.is_empty() {
+                encoder.finish().unwrap();
+                break;
+            }
+            let static_data: &'static [u8] = Box::leak(png_data.into_boxed_slice());
+            encoder
+                .push_png_buffer_with_timestamp(static_data, timestamp)
+                .unwrap();
+        }
+    });
+
+    let mut active_webview = false;
+
+    event_loop.run(move |event, _, control_flow| {
+        *control_flow = ControlFlow::Poll;
+
+        let now = Instant::now();
+
+        if (now.duration_since(last_frame_time) >= frame_duration) && active_webview {
+            let tx_clone = tx.clone();
+            webview
+                .take_snapshot(None, move |result| {
+                    let png_data = match result {
+                        Ok(png_data) => png_data,
+                        Err(e) => {
+                            eprintln!("Error taking snapshot: {}", e);
+                            Vec::new()
+                        }
+                    };
+
+                    let timestamp_ns = count as u64 * (1_000_000_000 / fps as u64);
+                    let _ = tx_clone.send((png_data, timestamp_ns));
+                })
+                .unwrap();
+
+            last_frame_time = now;
             count += 1;
-            println!("{} / {}", start_time.elapsed().as_secs(), count);
         }
 
         match event {
@@ -290,6 +939,7 @@ fn run_record(width: u32, height: u32, fps: u16, verbosity: u8) -> wry::Result<(
                 event: WindowEvent::CloseRequested,
                 ..
             } => {
+                let _ = tx.send((Vec::new(), 0u64));
                 *control_flow = ControlFlow::Exit;
             }
             Event::RedrawRequested(_) => {
@@ -305,20 +955,116 @@ use gstreamer::prelude::*;
 use gstreamer_app as gst_app;
 use gstreamer_video as gst_video;
 
-pub struct PngVideoEncoder {
+/// Where the encoded pipeline ends up: a muxed file on disk, or a live NDI source.
+pub enum VideoSink {
+    File {
+        path: String,
+        codec_options: CodecOptions,
+    },
+    Ndi {
+        name: String,
+        clock_sync: bool,
+    },
+}
+
+/// Resolve `threads == 0` to "one thread per CPU".
+fn resolve_threads(threads: u32) -> u32 {
+    if threads == 0 {
+        num_cpus::get() as u32
+    } else {
+        threads
+    }
+}
+
+/// Build the codec-specific encoder + parser pair for the `File` sink, honoring the
+/// quality/speed knobs threaded in from the `Record` CLI args.
+fn build_video_encoder(
+    opts: &CodecOptions,
+) -> Result<(gst::Element, gst::Element), Box<dyn std::error::Error>> {
+    let threads = resolve_threads(opts.threads);
+
+    match opts.codec {
+        Codec::H264 => {
+            let parse = gst::ElementFactory::make("h264parse").build()?;
+            let encoder = gst::ElementFactory::make("x264enc")
+                .build()
+                .or_else(|_| gst::ElementFactory::make("nvh264enc").build()) // NVIDIA
+                .or_else(|_| gst::ElementFactory::make("vaapih264enc").build()) // Intel/AMD
+                .or_else(|_| gst::ElementFactory::make("avenc_libx264").build())?; // FFmpeg
+
+            let mut option_string = format!("crf={}:threads={}", opts.crf, threads);
+            if let Some(delay) = opts.max_frame_delay {
+                option_string.push_str(&format!(":rc-lookahead={}", delay));
+            }
+            encoder.set_property("option-string", &option_string);
+
+            Ok((encoder, parse))
+        }
+        Codec::H265 => {
+            let parse = gst::ElementFactory::make("h265parse").build()?;
+            let encoder = gst::ElementFactory::make("x265enc")
+                .build()
+                .or_else(|_| gst::ElementFactory::make("nvh265enc").build()) // NVIDIA
+                .or_else(|_| gst::ElementFactory::make("vaapih265enc").build()) // Intel/AMD
+                .or_else(|_| gst::ElementFactory::make("qsvh265enc").build()) // Intel QuickSync
+                .or_else(|_| gst::ElementFactory::make("avenc_libx265").build()) // FFmpeg libx265
+                .or_else(|_| gst::ElementFactory::make("avenc_hevc_nvenc").build())?; // FFmpeg NVIDIA
+
+            let mut option_string = format!("crf={}:threads={}", opts.crf, threads);
+            if let Some(delay) = opts.max_frame_delay {
+                option_string.push_str(&format!(":rc-lookahead={}", delay));
+            }
+            encoder.set_property("option-string", &option_string);
+
+            Ok((encoder, parse))
+        }
+        Codec::Av1 => {
+            let parse = gst::ElementFactory::make("av1parse").build()?;
+            let encoder = gst::ElementFactory::make("rav1eenc")
+                .build()
+                .or_else(|_| gst::ElementFactory::make("av1enc").build())?;
+
+            if encoder.has_property("threads", None) {
+                encoder.set_property("threads", &(threads as i32));
+            }
+            if encoder.has_property("qp", None) {
+                encoder.set_property("qp", &(opts.crf as i32));
+            }
+            if let Some(delay) = opts.max_frame_delay {
+                if encoder.has_property("max-frame-delay", None) {
+                    encoder.set_property("max-frame-delay", &(delay as i32));
+                }
+            }
+
+            Ok((encoder, parse))
+        }
+    }
+}
+
+/// Feeds captured frames into `sink` via a `pngdec ! videoconvert ! <encoder or ndisink>`
+/// pipeline; the NDI sink takes the converted raw video without an encoder.
+///
+/// This always takes PNG-encoded frames (decoding them back to raw video internally) rather
+/// than a zero-copy raw-RGBA path: `wry`'s `take_snapshot` is the only frame source on every
+/// platform this crate supports, and it only hands back PNG bytes, so there's no raw
+/// framebuffer to feed a zero-copy path with. Deferred pending a capture API that exposes
+/// raw pixels.
+pub struct VideoEncoder {
     pipeline: gst::Pipeline,
     appsrc: gst_app::AppSrc,
+    audio_appsrc: Option<gst_app::AppSrc>,
     width: u32,
     height: u32,
     framerate: gst::Fraction,
 }
 
-impl PngVideoEncoder {
+impl VideoEncoder {
     pub fn new(
-        output_path: &str,
+        sink: VideoSink,
         width: u32,
         height: u32,
         framerate: gst::Fraction,
+        audio: bool,
     ) -> Result<Self, Box<dyn std::error::Error>> {
         gst::init()?;
 
@@ -330,26 +1076,9 @@ impl PngVideoEncoder {
             .downcast::<gst_app::AppSrc>()
             .unwrap();
 
-        let h265parse = gst::ElementFactory::make("h265parse").build()?;
         let pngdec = gst::ElementFactory::make("pngdec").build()?;
         let videoconvert = gst::ElementFactory::make("videoconvert").build()?;
 
-        let encoder = gst::ElementFactory::make("x265enc")
-            .build()
-            .or_else(|_| gst::ElementFactory::make("nvh265enc").build()) // NVIDIA
-            .or_else(|_| gst::ElementFactory::make("vaapih265enc").build()) // Intel/AMD
-            .or_else(|_| gst::ElementFactory::make("qsvh265enc").build()) // Intel QuickSync
-            .or_else(|_| gst::ElementFactory::make("avenc_libx265").build()) // FFmpeg libx265
-            .or_else(|_| gst::ElementFactory::make("avenc_hevc_nvenc").build()) // FFmpeg NVIDIA
-            .or_else(|_| {
-                gst::ElementFactory::make("x264enc")
-                    .property_from_str("speed-preset", "slow")
-                    .build()
-            })?;
-
-        let muxer = gst::ElementFactory::make("matroskamux").build()?;
-        let filesink = gst::ElementFactory::make("filesink").build()?;
-
         // Configure appsrc
         let caps = gst::Caps::builder("image/png")
             .field("width", width as i32)
@@ -362,36 +1091,100 @@ impl PngVideoEncoder {
         appsrc.set_property("is-live", &true);
         appsrc.set_property("stream-type", &gst_app::AppStreamType::Stream);
 
-        encoder.set_property("option-string", &"crf=18:threads=0");
-
-        // Configure file sink
-        filesink.set_property("location", &output_path);
-
-        // Add elements to pipeline
         pipeline.add_many(&[
             &appsrc.clone().upcast::<gst::Element>(),
             &pngdec,
             &videoconvert,
-            &h265parse,
-            &encoder,
-            &muxer,
-            &filesink,
         ])?;
-
-        // Link elements
         gst::Element::link_many(&[
             &appsrc.clone().upcast::<gst::Element>(),
             &pngdec,
             &videoconvert,
-            &encoder,
-            &h265parse,
-            &muxer,
-            &filesink,
         ])?;
 
-        Ok(PngVideoEncoder {
+        let mut audio_appsrc: Option<gst_app::AppSrc> = None;
+
+        match sink {
+            VideoSink::File {
+                path,
+                codec_options,
+            } => {
+                let (encoder, parse) = build_video_encoder(&codec_options)?;
+
+                let muxer = gst::ElementFactory::make("matroskamux").build()?;
+                let filesink = gst::ElementFactory::make("filesink").build()?;
+
+                filesink.set_property("location", &path);
+
+                pipeline.add_many(&[&encoder, &parse, &muxer, &filesink])?;
+                gst::Element::link_many(&[&videoconvert, &encoder, &parse, &muxer, &filesink])?;
+
+                if audio {
+                    let audio_src = gst::ElementFactory::make("appsrc")
+                        .build()?
+                        .downcast::<gst_app::AppSrc>()
+                        .unwrap();
+
+                    let audio_caps = gst::Caps::builder("audio/x-raw")
+                        .field("format", &"S16LE")
+                        .field("channels", &2i32)
+                        .field("rate", &48000i32)
+                        .field("layout", &"interleaved")
+                        .build();
+
+                    audio_src.set_property("caps", &audio_caps);
+                    audio_src.set_property("format", &gst::Format::Time);
+                    audio_src.set_property("is-live", &true);
+                    audio_src.set_property("stream-type", &gst_app::AppStreamType::Stream);
+
+                    let audioconvert = gst::ElementFactory::make("audioconvert").build()?;
+                    let audioenc = gst::ElementFactory::make("opusenc")
+                        .build()
+                        .or_else(|_| gst::ElementFactory::make("avenc_aac").build())?;
+
+                    pipeline.add_many(&[
+                        &audio_src.clone().upcast::<gst::Element>(),
+                        &audioconvert,
+                        &audioenc,
+                    ])?;
+                    gst::Element::link_many(&[
+                        &audio_src.clone().upcast::<gst::Element>(),
+                        &audioconvert,
+                        &audioenc,
+                    ])?;
+
+                    let audio_pad = muxer
+                        .request_pad_simple("audio_%u")
+                        .ok_or("matroskamux has no audio pad template")?;
+                    let audioenc_src_pad = audioenc
+                        .static_pad("src")
+                        .ok_or("audio encoder has no src pad")?;
+                    audioenc_src_pad.link(&audio_pad)?;
+
+                    audio_appsrc = Some(audio_src);
+                }
+            }
+            VideoSink::Ndi { name, clock_sync } => {
+                // NDI carries near-lossless video itself, so there's no point re-encoding
+                // into x265 just to have the NDI runtime decode it again downstream.
+                let ndisink = gst::ElementFactory::make("ndisink").build()?;
+                ndisink.set_property("ndi-name", &name);
+
+                if clock_sync {
+                    if let Some(clock) = ndisink.provide_clock() {
+                        pipeline.use_clock(Some(&clock));
+                    }
+                }
+
+                pipeline.add_many(&[&ndisink])?;
+                gst::Element::link_many(&[&videoconvert, &ndisink])?;
+            }
+        }
+
+        Ok(VideoEncoder {
             pipeline,
             appsrc,
+            audio_appsrc,
             width,
             height,
             framerate,
@@ -430,6 +1223,32 @@ impl PngVideoEncoder {
         }
     }
 
+    /// Push an audio chunk timestamped against the same `ClockTime` base as the video
+    /// frames, so the muxed track stays in sync with `push_png_buffer_with_timestamp`.
+    pub fn push_audio_buffer_with_timestamp(
+        &self,
+        audio_data: &'static [u8],
+        timestamp_ns: u64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let audio_appsrc = self
+            .audio_appsrc
+            .as_ref()
+            .ok_or("audio capture was not enabled for this encoder")?;
+
+        let mut buffer = gst::Buffer::from_slice(audio_data);
+        {
+            let buffer_ref = buffer.get_mut().unwrap();
+            buffer_ref.set_pts(gst::ClockTime::from_nseconds(timestamp_ns));
+        }
+
+        match audio_appsrc.push_buffer(buffer) {
+            Ok(_) => Ok(()),
+            Err(gst::FlowError::Flushing) => Err("Audio pipeline is flushing".into()),
+            Err(gst::FlowError::Eos) => Err("Audio end of stream".into()),
+            Err(err) => Err(format!("Failed to push audio buffer: {:?}", err).into()),
+        }
+    }
+
     pub fn push_png_buffer(
         &self,
         png_data: &'static [u8],
@@ -449,6 +1268,9 @@ impl PngVideoEncoder {
 
     pub fn finish(&self) -> Result<(), Box<dyn std::error::Error>> {
         self.appsrc.end_of_stream()?;
+        if let Some(audio_appsrc) = &self.audio_appsrc {
+            audio_appsrc.end_of_stream()?;
+        }
 
         // Wait for EOS
         let bus = self.pipeline.bus().unwrap();